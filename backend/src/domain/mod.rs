@@ -0,0 +1,3 @@
+mod subscriber_email;
+
+pub use subscriber_email::SubscriberEmail;
@@ -0,0 +1,43 @@
+use validator::ValidateEmail;
+
+/// A validated email address used as the sender for outgoing transactional mail.
+#[derive(Debug, Clone)]
+pub struct SubscriberEmail(String);
+
+impl SubscriberEmail {
+    /// Parses `s` into a `SubscriberEmail`, rejecting empty or malformed addresses.
+    pub fn parse(s: String) -> Result<Self, String> {
+        if s.validate_email() {
+            Ok(Self(s))
+        } else {
+            Err(format!("{} is not a valid subscriber email.", s))
+        }
+    }
+}
+
+impl AsRef<str> for SubscriberEmail {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubscriberEmail;
+
+    #[test]
+    fn empty_string_is_rejected() {
+        assert!(SubscriberEmail::parse("".to_string()).is_err());
+    }
+
+    #[test]
+    fn missing_at_symbol_is_rejected() {
+        assert!(SubscriberEmail::parse("not-an-email".to_string()).is_err());
+    }
+
+    #[test]
+    fn valid_email_is_accepted() {
+        let email = SubscriberEmail::parse("sender@example.com".to_string()).unwrap();
+        assert_eq!(email.as_ref(), "sender@example.com");
+    }
+}
@@ -1,5 +1,9 @@
+use secrecy::{ExposeSecret, Secret};
+use serde_aux::field_attributes::deserialize_number_from_string;
 use sqlx::ConnectOptions;
 
+use crate::domain::SubscriberEmail;
+
 /// Global settings for exposing all preconfigured variables
 #[derive(serde::Deserialize, Clone)]
 pub struct Settings {
@@ -7,6 +11,24 @@ pub struct Settings {
     pub debug: bool,
     pub database: DatabaseSettings,
     pub redis: RedisSettings,
+    pub email_client: EmailClientSettings,
+}
+
+impl Settings {
+    /// The externally reachable address of the application, computed from
+    /// `application.base_url` and `application.port`.
+    ///
+    /// In debug mode this is `base_url:port`, since the app is typically
+    /// reached directly on its port during development; otherwise it's just
+    /// `base_url`, since production traffic is expected to be fronted by a
+    /// reverse proxy on the standard port.
+    pub fn web_address(&self) -> String {
+        if self.debug {
+            format!("{}:{}", self.application.base_url, self.application.port)
+        } else {
+            self.application.base_url.clone()
+        }
+    }
 }
 
 /// Application's specific settings to expose `port`,
@@ -14,10 +36,12 @@ pub struct Settings {
 /// during and after development
 #[derive(serde::Deserialize, Clone)]
 pub struct ApplicationSettings {
+    #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
     pub base_url: String,
     pub protocol: String,
+    pub hmac_secret: Secret<String>,
 }
 
 /// The possible runtime environment for our application.
@@ -40,10 +64,10 @@ impl TryFrom<String> for Environment {
 
     fn try_from(s: String) -> Result<Self, Self::Error> {
         match s.to_lowercase().as_str() {
-            "development" => Ok(Self::Development),
-            "production" => Ok(Self::Production),
+            "development" | "dev" => Ok(Self::Development),
+            "production" | "prod" => Ok(Self::Production),
             other => Err(format!(
-                "{} is not a supported environment. Use either `development` or `production`.",
+                "{} is not a supported environment. Use either `development`, `dev`, `production`, or `prod`.",
                 other
             )),
         }
@@ -94,18 +118,48 @@ pub fn get_settings() -> Result<Settings, config::ConfigError> {
 /// Redis settings for the entire app
 #[derive(serde::Deserialize, Clone, Debug)]
 pub struct RedisSettings {
-    pub uri: String,
+    pub uri: Secret<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
     pub pool_max_open: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
     pub pool_max_idle: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
     pub pool_timeout_seconds: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
     pub pool_expire_seconds: u64,
 }
 
+/// Email client settings used to dispatch registration confirmation,
+/// password reset, and other transactional mail through a configured
+/// HTTP email client.
+#[derive(serde::Deserialize, Clone)]
+pub struct EmailClientSettings {
+    pub base_url: String,
+    pub sender_email: String,
+    pub authorization_token: Secret<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub timeout_milliseconds: u64,
+}
+
+impl EmailClientSettings {
+    /// Parses `sender_email` into a validated [`SubscriberEmail`], failing fast
+    /// if the configured address is empty or malformed.
+    pub fn sender(&self) -> Result<SubscriberEmail, String> {
+        SubscriberEmail::parse(self.sender_email.clone())
+    }
+
+    /// The configured email client timeout.
+    pub fn timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.timeout_milliseconds)
+    }
+}
+
 /// Database settings for the entire app
 #[derive(serde::Deserialize, Clone)]
 pub struct DatabaseSettings {
     pub username: String,
-    pub password: String,
+    pub password: Secret<String>,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
     pub database_name: String,
@@ -113,19 +167,26 @@ pub struct DatabaseSettings {
 }
 
 impl DatabaseSettings {
-    pub fn connect_to_db(&self) -> sqlx::postgres::PgConnectOptions {
+    /// Connect options for the Postgres instance itself, without selecting a
+    /// specific database. Used to `CREATE DATABASE` for ephemeral test
+    /// databases and first-run provisioning.
+    pub fn without_db(&self) -> sqlx::postgres::PgConnectOptions {
         let ssl_mode = if self.require_ssl {
             sqlx::postgres::PgSslMode::Require
         } else {
             sqlx::postgres::PgSslMode::Prefer
         };
-        let mut options = sqlx::postgres::PgConnectOptions::new()
+        sqlx::postgres::PgConnectOptions::new()
             .host(&self.host)
             .username(&self.username)
-            .password(&self.password)
+            .password(self.password.expose_secret())
             .port(self.port)
             .ssl_mode(ssl_mode)
-            .database(&self.database_name);
+    }
+
+    /// Connect options for `database_name`, layered on top of [`Self::without_db`].
+    pub fn with_db(&self) -> sqlx::postgres::PgConnectOptions {
+        let mut options = self.without_db().database(&self.database_name);
         options.log_statements(tracing::log::LevelFilter::Trace);
         options
     }